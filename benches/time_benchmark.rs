@@ -1,4 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
 #[cfg(target_arch = "x86_64")]
@@ -71,6 +73,31 @@ fn bench_rdtsc_for_ts(c: &mut Criterion) {
     });
 }
 
+/// 后台 upkeep 线程每 1ms 刷新一次缓存的 `AtomicU64`，调用方只做一次
+/// relaxed load，衡量这种方案相对 vDSO/TSC 路径的开销下限。
+static UPKEEP_NANOS: AtomicU64 = AtomicU64::new(0);
+
+fn ensure_upkeep_thread() {
+    static STARTED: OnceLock<()> = OnceLock::new();
+    STARTED.get_or_init(|| {
+        std::thread::spawn(|| loop {
+            let now = clock_gettime_us(CLOCK_MONOTONIC) * 1000; // 近似纳秒
+            UPKEEP_NANOS.store(now, Ordering::Relaxed);
+            std::thread::sleep(Duration::from_millis(1));
+        });
+    });
+}
+
+/// Benchmark upkeep 缓存时钟的 relaxed load
+fn bench_upkeep_now(c: &mut Criterion) {
+    ensure_upkeep_thread();
+    // 等待后台线程完成首次写入，避免基准测量到恒定的 0
+    std::thread::sleep(Duration::from_millis(5));
+    c.bench_function("upkeep_now", |b| {
+        b.iter(|| black_box(UPKEEP_NANOS.load(Ordering::Relaxed)))
+    });
+}
+
 fn bench_instance_now(c: &mut Criterion) {
     c.bench_function("instance_now", |b| {
         b.iter(|| black_box(Instant::now().elapsed().as_secs()))
@@ -91,6 +118,44 @@ fn bench_clock_monotonic(c: &mut Criterion) {
     });
 }
 
+/// 直接通过解析出的 `__vdso_clock_gettime` 函数指针调用，对照
+/// `bench_clock_monotonic` 衡量跳过 libc 包装能省下多少开销。
+/// ELF/GNU hash 解析逻辑在 `vdso_test::vdso_resolve` 里，和
+/// `src/tsc_vs_vdso.rs` 共用同一份实现。
+#[cfg(target_os = "linux")]
+fn bench_vdso_cgt_clock(c: &mut Criterion) {
+    use vdso_test::vdso_resolve;
+
+    let f = vdso_resolve::clock_gettime_fn();
+    c.bench_function("vdso_cgt_clock", |b| {
+        b.iter(|| {
+            let mut ts: timespec = unsafe { std::mem::zeroed() };
+            let ret = match f {
+                Some(f) => unsafe { f(CLOCK_MONOTONIC, &mut ts as *mut _) },
+                None => unsafe { clock_gettime(CLOCK_MONOTONIC, &mut ts as *mut _) },
+            };
+            black_box(ret);
+            black_box(ts.tv_sec)
+        })
+    });
+}
+
+/// Benchmark clock_gettime with CLOCK_MONOTONIC_COARSE（jiffy 级精度，预期显著快于 fine-grained 版本）
+#[cfg(target_os = "linux")]
+fn bench_clock_monotonic_coarse(c: &mut Criterion) {
+    c.bench_function("clock_gettime_monotonic_coarse", |b| {
+        b.iter(|| black_box(clock_gettime_us(libc::CLOCK_MONOTONIC_COARSE)))
+    });
+}
+
+/// Benchmark clock_gettime with CLOCK_REALTIME_COARSE
+#[cfg(target_os = "linux")]
+fn bench_clock_realtime_coarse(c: &mut Criterion) {
+    c.bench_function("clock_gettime_realtime_coarse", |b| {
+        b.iter(|| black_box(clock_gettime_us(libc::CLOCK_REALTIME_COARSE)))
+    });
+}
+
 /// Benchmark chrono::Utc::now()
 fn bench_chrono(c: &mut Criterion) {
     c.bench_function("chrono_utc_now", |b| {
@@ -228,9 +293,25 @@ criterion_group!(
     bench_clock_monotonic,
     bench_chrono,
     bench_instance_now,
+    bench_upkeep_now,
     // bench_time_methods_comparison,
     // bench_time_methods_with_iterations,
     // bench_time_methods_cache_effects
 );
 
+// `criterion_group!`'s macro arguments can't carry a `#[cfg(...)]` themselves
+// (it expects a bare `$target:path` list), so the Linux-only benches get
+// their own group instead of being cfg'd out of the list above.
+#[cfg(target_os = "linux")]
+criterion_group!(
+    linux_benches,
+    bench_clock_monotonic_coarse,
+    bench_clock_realtime_coarse,
+    bench_vdso_cgt_clock,
+);
+
+#[cfg(target_os = "linux")]
+criterion_main!(benches, linux_benches);
+
+#[cfg(not(target_os = "linux"))]
 criterion_main!(benches);
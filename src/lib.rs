@@ -0,0 +1,6 @@
+//! 共享给各个 bin/bench 的代码：目前只有直接解析 vDSO 符号表的 `vdso_resolve`，
+//! 避免 `src/tsc_vs_vdso.rs` 和 `benches/time_benchmark.rs` 各自维护一份
+//! 同样的 unsafe ELF 解析逻辑。
+
+#[cfg(target_os = "linux")]
+pub mod vdso_resolve;
@@ -4,7 +4,15 @@ use libc::{clock_gettime, timespec, CLOCK_REALTIME};
 // [features]
 // tsc = []   # 仅在 linux+x86_64 有效
 
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "tsc"
+))]
+use std::sync::atomic::AtomicU32;
+use std::sync::{Arc, OnceLock};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 #[derive(Clone, Copy)]
@@ -15,17 +23,61 @@ struct Base {
 
 static BASE: OnceLock<Base> = OnceLock::new();
 
+/// 调用方在精度与开销之间的取舍：`Fine` 走 vDSO/TSC 的精细路径
+/// （~微秒级精度，几十纳秒开销）；`Coarse` 走内核的 `_COARSE` clock
+/// 变体（jiffy 级精度，约 1~4ms，开销只有 `Fine` 的几分之一），
+/// 适合日志、限流这类不需要精确时间戳的调用方。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Precision {
+    Fine,
+    Coarse,
+}
+
+#[inline]
+pub fn now_wall(precision: Precision) -> Duration {
+    match precision {
+        Precision::Fine => {
+            let base = *BASE.get_or_init(init_base);
+            base.wall_base + (now_mono(Precision::Fine) - base.mono_base)
+        }
+        Precision::Coarse => vdso_clock_gettime_realtime_coarse(),
+    }
+}
+
+#[inline]
+pub fn now_mono(precision: Precision) -> Duration {
+    match precision {
+        Precision::Fine => platform_now_mono(),
+        Precision::Coarse => vdso_clock_gettime_monotonic_coarse(),
+    }
+}
+
+// ---------- _COARSE 变体：跳过 TSC 校准，直接走内核 jiffy 时钟 ----------
+#[cfg(target_os = "linux")]
 #[inline]
-pub fn now_wall() -> Duration {
-    let base = *BASE.get_or_init(init_base);
-    base.wall_base + (now_mono() - base.mono_base)
+fn vdso_clock_gettime_monotonic_coarse() -> Duration {
+    raw_clock_gettime(libc::CLOCK_MONOTONIC_COARSE)
 }
 
+#[cfg(not(target_os = "linux"))]
 #[inline]
-pub fn now_mono() -> Duration {
+fn vdso_clock_gettime_monotonic_coarse() -> Duration {
+    // 非 Linux 平台没有 _COARSE 变体，退回精细路径。
     platform_now_mono()
 }
 
+#[cfg(target_os = "linux")]
+#[inline]
+fn vdso_clock_gettime_realtime_coarse() -> Duration {
+    raw_clock_gettime(libc::CLOCK_REALTIME_COARSE)
+}
+
+#[cfg(not(target_os = "linux"))]
+#[inline]
+fn vdso_clock_gettime_realtime_coarse() -> Duration {
+    now_wall(Precision::Fine)
+}
+
 // ---------- 初始化：启动时记录一次 wall & mono ----------
 fn init_base() -> Base {
     let wall = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
@@ -36,6 +88,154 @@ fn init_base() -> Base {
     }
 }
 
+// ==========================================================
+// Clock trait：可替换成 mock 时钟的时间源（参考 quanta 的 mock 模块）
+// ==========================================================
+// `now_wall`/`now_mono` 这两个自由函数直接绑死了真实时钟，依赖这个
+// crate 的下游代码很难在单测里做到“时间可控”。`Clock` trait 把时间源
+// 抽象出来：`RealClock` 转发到上面的自由函数，`MockClock` 则由一个
+// `Arc<AtomicU64>` 驱动，测试可以用 `increment`/`set` 直接拨动时间，
+// 不需要真的睡眠。
+
+/// 统一的时间源接口，调用方不关心背后是真实时钟还是测试用的 mock。
+pub trait Clock {
+    fn now_mono(&self) -> Duration;
+    fn now_wall(&self) -> Duration;
+}
+
+/// 生产环境使用的时钟：转发到 `now_mono`/`now_wall`（`Precision::Fine`）。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    #[inline]
+    fn now_mono(&self) -> Duration {
+        now_mono(Precision::Fine)
+    }
+
+    #[inline]
+    fn now_wall(&self) -> Duration {
+        now_wall(Precision::Fine)
+    }
+}
+
+/// 由 `Arc<AtomicU64>` 驱动的可控时钟，供测试确定性地推进时间。
+/// `now_mono`/`now_wall` 返回同一个计数，因为测试通常不关心两者的
+/// 差异，只需要一个能被拨动的时间轴。
+#[derive(Clone, Debug, Default)]
+pub struct MockClock {
+    nanos: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    /// 构造一对共享同一时间轴的 `MockClock`：第一个交给被测代码当
+    /// `Clock` 用，第二个留在测试里通过 `increment`/`set` 驱动时间。
+    pub fn handle_pair() -> (MockClock, MockClock) {
+        let nanos = Arc::new(AtomicU64::new(0));
+        (
+            MockClock {
+                nanos: Arc::clone(&nanos),
+            },
+            MockClock { nanos },
+        )
+    }
+
+    /// 把时间向前拨动 `duration`。
+    pub fn increment(&self, duration: Duration) {
+        self.nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// 把时间设置为距离起点 `duration`。
+    pub fn set(&self, duration: Duration) {
+        self.nanos
+            .store(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Clock for MockClock {
+    #[inline]
+    fn now_mono(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    fn now_wall(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+}
+
+// ==========================================================
+// Upkeep：后台线程缓存时钟（参考 metrics-rs/quanta 的 upkeep 模块）
+// ==========================================================
+// 对高 QPS 调用方来说，每次都走 `now_mono`/`now_wall` 仍要付一次系统调用
+// （或 TSC 校准查表）的开销。`Upkeep` 启动一个后台线程，按固定间隔刷新
+// 两个全局 `AtomicU64`，调用方通过 `now_mono_coarse`/`now_wall_coarse`
+// 做一次 relaxed load 即可拿到时间戳，代价是精度受刷新间隔限制。
+
+static UPKEEP_MONO_NANOS: AtomicU64 = AtomicU64::new(0);
+static UPKEEP_WALL_NANOS: AtomicU64 = AtomicU64::new(0);
+
+#[inline]
+fn store_coarse_now() {
+    UPKEEP_MONO_NANOS.store(now_mono(Precision::Fine).as_nanos() as u64, Ordering::Relaxed);
+    UPKEEP_WALL_NANOS.store(now_wall(Precision::Fine).as_nanos() as u64, Ordering::Relaxed);
+}
+
+/// 读取 upkeep 线程缓存的单调时间，仅一次 relaxed load，亚纳秒级开销。
+///
+/// 在 `Upkeep` 启动之前调用会拿到 0；陈旧度受 `Upkeep::start` 的
+/// `interval` 参数限制。
+#[inline]
+pub fn now_mono_coarse() -> Duration {
+    Duration::from_nanos(UPKEEP_MONO_NANOS.load(Ordering::Relaxed))
+}
+
+/// 读取 upkeep 线程缓存的挂钟时间，语义同 [`now_mono_coarse`]。
+#[inline]
+pub fn now_wall_coarse() -> Duration {
+    Duration::from_nanos(UPKEEP_WALL_NANOS.load(Ordering::Relaxed))
+}
+
+/// 后台 upkeep 线程的句柄：持有期间线程持续刷新缓存时钟，
+/// drop 时通知线程退出并 join，保证不留后台线程。
+pub struct Upkeep {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Upkeep {
+    /// 启动后台线程，每隔 `interval` 刷新一次缓存时钟。
+    pub fn start(interval: Duration) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_thread = Arc::clone(&stop);
+        // 启动前先同步写一次，避免调用方在第一个 interval 内读到 0。
+        store_coarse_now();
+        let handle = std::thread::Builder::new()
+            .name("vdso-test-upkeep".to_string())
+            .spawn(move || {
+                while !stop_thread.load(Ordering::Relaxed) {
+                    std::thread::sleep(interval);
+                    store_coarse_now();
+                }
+            })
+            .expect("failed to spawn upkeep thread");
+        Upkeep {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Upkeep {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
 // ==========================================================
 // 平台实现：单调时间（尽量走最快路径）
 // ==========================================================
@@ -43,10 +243,15 @@ fn init_base() -> Base {
 // ---------- Linux ----------
 #[cfg(target_os = "linux")]
 fn platform_now_mono() -> Duration {
-    // 优先：在 linux+x86_64 且启用 feature "tsc" 时，走 RDTSC 极致路径
-    #[cfg(all(target_arch = "x86_64", feature = "tsc"))]
+    // 优先：在 linux+x86_64/aarch64 且启用 feature "tsc" 时，走硬件计数
+    // 器极致路径——但 x86_64 上前提是运行时探测到 invariant TSC，否则
+    // 即使编译了该 feature 也静默退回 vDSO 路径，避免在频率会变的 CPU
+    // 上产生错误的时间戳（aarch64 的 generic timer 架构上总是可信）。
+    #[cfg(all(any(target_arch = "x86_64", target_arch = "aarch64"), feature = "tsc"))]
     {
-        return tsc_now_mono();
+        if tsc_is_reliable() {
+            return tsc_now_mono();
+        }
     }
     // 其他情况：走 vDSO 的 clock_gettime(CLOCK_MONOTONIC)
     vdso_clock_gettime_monotonic()
@@ -55,11 +260,45 @@ fn platform_now_mono() -> Duration {
 #[cfg(target_os = "linux")]
 #[inline]
 fn vdso_clock_gettime_monotonic() -> Duration {
-    use libc::{clock_gettime, timespec, CLOCK_MONOTONIC};
+    raw_clock_gettime(libc::CLOCK_MONOTONIC)
+}
+
+// ==========================================================
+// 直接解析 vDSO 符号表，绕开 libc::clock_gettime 的 PLT/wrapper 开销
+// ==========================================================
+// 上面几个 `vdso_clock_gettime_*` 函数名字里带“vdso”，但以前走的是
+// libc 的 `clock_gettime` 包装——内核的 vDSO 会不会被命中完全取决于
+// glibc/musl 内部有没有把这个符号指向 vDSO 映射，我们自己并不知道。
+// 现在它们都经由下面的 `raw_clock_gettime`，优先直接调用解析出的符号。
+// 实际的 ELF/GNU hash 解析逻辑放在 `vdso_resolve` 模块里（`src/lib.rs`
+// 导出），`benches/time_benchmark.rs` 也复用同一份实现，避免这段 unsafe
+// 代码被复制维护两份。
+#[cfg(target_os = "linux")]
+use vdso_test::vdso_resolve;
+
+/// 所有 `vdso_clock_gettime_*` 的公共读取路径：优先用直接解析出的
+/// `__vdso_clock_gettime` 函数指针；如果没拿到（比如 seccomp 下 vDSO
+/// 被禁用）且请求的是墙上时间，再退一步试 `__vdso_gettimeofday`；
+/// 两者都拿不到时退回 `libc::clock_gettime`。
+#[cfg(target_os = "linux")]
+#[inline]
+fn raw_clock_gettime(clock: libc::clockid_t) -> Duration {
+    if let Some(f) = vdso_resolve::clock_gettime_fn() {
+        let mut ts: timespec = unsafe { std::mem::zeroed() };
+        if unsafe { f(clock, &mut ts as *mut _) } == 0 {
+            return Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32);
+        }
+    } else if clock == libc::CLOCK_REALTIME || clock == libc::CLOCK_REALTIME_COARSE {
+        if let Some(g) = vdso_resolve::gettimeofday_fn() {
+            let mut tv: libc::timeval = unsafe { std::mem::zeroed() };
+            if unsafe { g(&mut tv as *mut _, std::ptr::null_mut()) } == 0 {
+                return Duration::new(tv.tv_sec as u64, tv.tv_usec as u32 * 1000);
+            }
+        }
+    }
     unsafe {
         let mut ts: timespec = std::mem::zeroed();
-        let ret = clock_gettime(CLOCK_MONOTONIC, &mut ts as *mut _);
-        if ret != 0 {
+        if clock_gettime(clock, &mut ts as *mut _) != 0 {
             // 极少数情况下出错，退回 0
             return Duration::from_nanos(0);
         }
@@ -67,41 +306,278 @@ fn vdso_clock_gettime_monotonic() -> Duration {
     }
 }
 
-// ---------- Linux x86_64: TSC 超快路径（可选） ----------
+// ---------- Linux x86_64/aarch64: 硬件计数器超快路径（可选） ----------
+//
+// 旧版用 `delta_cycles as f64 / cycles_per_sec` 换算纳秒，每次调用都有一次
+// f64 除法，长时间运行还会累积舍入误差。这里改用内核/DragonOS timekeeping
+// 的整数 mult/shift 方案：`nanos = (cycles * mult) >> shift`，只有加法、
+// 乘法和移位。`mult` 会被一个 NTP 风格反馈环（见 `resync_tsc_calib`，
+// 由读取线程每 `TSC_RESYNC_EVERY` 次读数就地触发一次）做有界微调，
+// 防止计数器频率与真值（vDSO MONOTONIC）长期漂移。`TscCalib`/`calc_mult_shift`/`project_nanos`/
+// `resync_tsc_calib` 与具体架构无关，x86_64 的 RDTSC 和 aarch64 的
+// CNTVCT_EL0 共用同一套投影逻辑，只有“怎么读计数器”“怎么校准频率”
+// 因架构而异。
+//
+// 在 x86_64 上这一切的前提是 TSC 本身可信：在没有 invariant/constant
+// TSC 的 CPU 上，计数速率会随频率调节（turbo/省电）漂移，再怎么校准也
+// 没用，通过 CPUID 做一次性探测，不可信时即使编译了 `tsc` feature 也要
+// 在运行时静默退回 vDSO 路径。aarch64 的 generic timer 架构上保证频率
+// 固定且跨核一致，不需要这层探测。
+
+/// CPUID leaf `0x8000_0007` EDX bit 8：invariant TSC。
 #[cfg(all(target_os = "linux", target_arch = "x86_64", feature = "tsc"))]
-fn tsc_now_mono() -> Duration {
-    // 用 OnceLock 做一次频率校准与基准记录
-    struct TscCalib {
-        cycles_per_sec: f64,
-        base_cycles: u64,
-        base_mono: Duration, // 与 TSC 对齐的单调时间基准
+fn cpuid_invariant_tsc() -> bool {
+    use std::arch::x86_64::__cpuid;
+    let max_extended = __cpuid(0x8000_0000).eax;
+    if max_extended < 0x8000_0007 {
+        return false;
+    }
+    let leaf = __cpuid(0x8000_0007);
+    leaf.edx & (1 << 8) != 0
+}
+
+/// CPUID leaf `0x8000_0001` EDX bit 27：是否支持 `RDTSCP`。
+#[cfg(all(target_os = "linux", target_arch = "x86_64", feature = "tsc"))]
+fn cpuid_rdtscp_available() -> bool {
+    use std::arch::x86_64::__cpuid;
+    let max_extended = __cpuid(0x8000_0000).eax;
+    if max_extended < 0x8000_0001 {
+        return false;
+    }
+    let leaf = __cpuid(0x8000_0001);
+    leaf.edx & (1 << 27) != 0
+}
+
+/// TSC 是否可以作为计时基准使用：当前仅要求 invariant TSC。探测结果
+/// 只需要做一次，缓存进 `OnceLock`。
+#[cfg(all(target_os = "linux", target_arch = "x86_64", feature = "tsc"))]
+pub fn tsc_is_reliable() -> bool {
+    static RELIABLE: OnceLock<bool> = OnceLock::new();
+    *RELIABLE.get_or_init(cpuid_invariant_tsc)
+}
+
+/// aarch64 的通用定时器（generic timer）架构上保证 `CNTVCT_EL0` 频率
+/// 固定、跨核一致，不存在 x86 那种 turbo/省电导致的频率漂移问题。
+#[cfg(all(target_os = "linux", target_arch = "aarch64", feature = "tsc"))]
+pub fn tsc_is_reliable() -> bool {
+    true
+}
+
+/// 读取 TSC 计数，尽量带序列化语义，避免乱序执行导致的计数错位：
+/// 有 `RDTSCP` 就用它（自带序列化），否则退化为 `lfence; rdtsc`。
+#[cfg(all(target_os = "linux", target_arch = "x86_64", feature = "tsc"))]
+#[inline]
+fn read_cycle_counter() -> u64 {
+    static RDTSCP_AVAILABLE: OnceLock<bool> = OnceLock::new();
+    unsafe {
+        if *RDTSCP_AVAILABLE.get_or_init(cpuid_rdtscp_available) {
+            let mut aux = 0u32;
+            core::arch::x86_64::__rdtscp(&mut aux)
+        } else {
+            core::arch::x86_64::_mm_lfence();
+            core::arch::x86_64::_rdtsc()
+        }
+    }
+}
+
+/// 读取 aarch64 的虚拟计数寄存器 `CNTVCT_EL0`。和 x86 的 RDTSC 一样，
+/// 计数器读数本身可以被乱序执行提前/推后，ARM ARM 建议在需要顺序
+/// 保证的场合在 `mrs` 前加一条 `isb` 把它钉在正确的位置；这里每次
+/// 读数都带上 `isb`，对应 x86 路径上 `RDTSCP`/`lfence` 的作用。
+#[cfg(all(target_os = "linux", target_arch = "aarch64", feature = "tsc"))]
+#[inline]
+fn read_cycle_counter() -> u64 {
+    let v: u64;
+    unsafe {
+        core::arch::asm!(
+            "isb",
+            "mrs {v}, cntvct_el0",
+            v = out(reg) v,
+            options(nomem, nostack)
+        );
+    }
+    v
+}
+
+/// 读取 aarch64 计数器的频率寄存器 `CNTFRQ_EL0`（Hz）。与 x86 的 TSC
+/// 不同，这个频率是架构直接暴露出来的精确值，不需要用睡眠窗口去估算。
+#[cfg(all(target_os = "linux", target_arch = "aarch64", feature = "tsc"))]
+#[inline]
+fn read_cntfrq() -> u64 {
+    let v: u64;
+    unsafe {
+        core::arch::asm!("mrs {v}, cntfrq_el0", v = out(reg) v, options(nomem, nostack));
+    }
+    v
+}
+
+/// 校准完成后固定不变的基准量，加上随时间被 resync 调整的 `mult`。
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "tsc"
+))]
+struct TscCalib {
+    base_cycles: u64,
+    base_mono_nanos: u64,
+    shift: u32,
+    mult: AtomicU32,
+    reads: AtomicU64,
+    // 迄今为止发布过的最大纳秒数，用于 clamp 保证单调不倒退。
+    last_nanos: AtomicU64,
+}
+
+/// 每隔多少次读数触发一次 resync（而不是每次都读 vDSO，否则就失去了
+/// 快速路径的速度优势）。
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "tsc"
+))]
+const TSC_RESYNC_EVERY: u64 = 100_000;
+
+/// 单次 resync 允许对 `mult` 做的最大相对调整幅度，避免误差较大时
+/// 时间戳发生明显跳变。
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "tsc"
+))]
+const TSC_MAX_MULT_CORRECTION_RATIO: f64 = 0.0005;
+
+/// 给定标定出的 `cycles_per_sec`，求一对 `(mult, shift)` 使得
+/// `(cycles * mult) >> shift` 近似 `cycles * 1e9 / cycles_per_sec`。
+/// 优先取尽量大的 shift（不超过 32）以保留精度，同时保证 `mult` 落在
+/// u32 范围内。
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "tsc"
+))]
+fn calc_mult_shift(cycles_per_sec: f64) -> (u32, u32) {
+    let ns_per_cycle = 1e9 / cycles_per_sec;
+    let mut shift = 32u32;
+    loop {
+        let mult = ns_per_cycle * (1u64 << shift) as f64;
+        if mult > 0.0 && mult <= u32::MAX as f64 {
+            return (mult.round() as u32, shift);
+        }
+        if shift == 0 {
+            return (ns_per_cycle.round().max(1.0) as u32, 0);
+        }
+        shift -= 1;
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "tsc"
+))]
+#[inline]
+fn project_nanos(c: &TscCalib, cycles: u64) -> u64 {
+    let delta_cycles = cycles.wrapping_sub(c.base_cycles);
+    let mult = c.mult.load(Ordering::Relaxed) as u128;
+    let delta_nanos = ((delta_cycles as u128 * mult) >> c.shift) as u64;
+    c.base_mono_nanos.wrapping_add(delta_nanos)
+}
+
+/// 以 vDSO MONOTONIC 为真值，测量计数器投影时间的误差，并对 `mult`
+/// 做一次有界的 NTP 风格纠偏，使快速路径的时钟不会无限漂移。
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "tsc"
+))]
+fn resync_tsc_calib(c: &TscCalib) {
+    let cycles = read_cycle_counter();
+    let true_nanos = vdso_clock_gettime_monotonic().as_nanos() as u64;
+    let delta_cycles = cycles.wrapping_sub(c.base_cycles);
+    if delta_cycles == 0 {
+        return;
     }
+    let projected_nanos = project_nanos(c, cycles);
+    let error_nanos = true_nanos as i64 - projected_nanos as i64;
+
+    let mult = c.mult.load(Ordering::Relaxed);
+    let correction = (error_nanos as f64 / delta_cycles as f64) * (1u64 << c.shift) as f64;
+    let max_step = mult as f64 * TSC_MAX_MULT_CORRECTION_RATIO;
+    let new_mult = (mult as f64 + correction.clamp(-max_step, max_step))
+        .round()
+        .clamp(1.0, u32::MAX as f64) as u32;
+    c.mult.store(new_mult, Ordering::Relaxed);
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64", feature = "tsc"))]
+fn tsc_now_mono() -> Duration {
     static CALIB: OnceLock<TscCalib> = OnceLock::new();
 
     let c = CALIB.get_or_init(|| {
-        // 以 vDSO 的 MONOTONIC 为“真值”，校准 TSC 频率
-        let start_cycles = unsafe { core::arch::x86_64::_rdtsc() };
+        // 以 vDSO 的 MONOTONIC 为“真值”，校准 TSC 频率。用带序列化语义的
+        // 读数，避免乱序执行把 start/end 采样点挪到睡眠窗口之外。
+        let start_cycles = read_cycle_counter();
         let start_mono = vdso_clock_gettime_monotonic();
         // 采用较短睡眠窗口，权衡启动延迟与精度
         std::thread::sleep(Duration::from_millis(50));
-        let end_cycles = unsafe { core::arch::x86_64::_rdtsc() };
+        let end_cycles = read_cycle_counter();
         let end_mono = vdso_clock_gettime_monotonic();
 
         let d_cycles = (end_cycles - start_cycles) as f64;
         let d_secs = (end_mono - start_mono).as_secs_f64();
-        let cps = if d_secs > 0.0 { d_cycles / d_secs } else { 0.0 };
+        let cps = if d_secs > 0.0 { d_cycles / d_secs } else { 1.0 };
+        let (mult, shift) = calc_mult_shift(cps.max(1.0));
 
         TscCalib {
-            cycles_per_sec: cps.max(1.0), // 防守式，避免被 0 除
             base_cycles: end_cycles,
-            base_mono: end_mono,
+            base_mono_nanos: end_mono.as_nanos() as u64,
+            shift,
+            mult: AtomicU32::new(mult),
+            reads: AtomicU64::new(0),
+            last_nanos: AtomicU64::new(end_mono.as_nanos() as u64),
+        }
+    });
+
+    if c.reads.fetch_add(1, Ordering::Relaxed).is_multiple_of(TSC_RESYNC_EVERY) {
+        resync_tsc_calib(c);
+    }
+
+    let now_cycles = read_cycle_counter();
+    let projected_nanos = project_nanos(c, now_cycles);
+    // clamp：即使 resync 把 mult 调小，也绝不允许时间戳倒退。
+    let prev_max = c.last_nanos.fetch_max(projected_nanos, Ordering::AcqRel);
+    Duration::from_nanos(prev_max.max(projected_nanos))
+}
+
+/// aarch64 版本的快速路径：`CNTFRQ_EL0` 直接给出精确频率，不需要像
+/// x86_64 那样靠睡眠窗口去估算 `cycles_per_sec`，首次调用的延迟也更低。
+#[cfg(all(target_os = "linux", target_arch = "aarch64", feature = "tsc"))]
+fn tsc_now_mono() -> Duration {
+    static CALIB: OnceLock<TscCalib> = OnceLock::new();
+
+    let c = CALIB.get_or_init(|| {
+        let freq_hz = read_cntfrq().max(1) as f64;
+        let (mult, shift) = calc_mult_shift(freq_hz);
+        let base_cycles = read_cycle_counter();
+        let base_mono = vdso_clock_gettime_monotonic();
+
+        TscCalib {
+            base_cycles,
+            base_mono_nanos: base_mono.as_nanos() as u64,
+            shift,
+            mult: AtomicU32::new(mult),
+            reads: AtomicU64::new(0),
+            last_nanos: AtomicU64::new(base_mono.as_nanos() as u64),
         }
     });
 
-    let now_cycles = unsafe { core::arch::x86_64::_rdtsc() };
-    let delta_cycles = now_cycles.wrapping_sub(c.base_cycles) as f64;
-    let delta_secs = delta_cycles / c.cycles_per_sec;
-    c.base_mono + Duration::from_secs_f64(delta_secs.max(0.0))
+    if c.reads.fetch_add(1, Ordering::Relaxed).is_multiple_of(TSC_RESYNC_EVERY) {
+        resync_tsc_calib(c);
+    }
+
+    let now_cycles = read_cycle_counter();
+    let projected_nanos = project_nanos(c, now_cycles);
+    let prev_max = c.last_nanos.fetch_max(projected_nanos, Ordering::AcqRel);
+    Duration::from_nanos(prev_max.max(projected_nanos))
 }
 
 // ---------- macOS（Intel 与 Apple Silicon 通用） ----------
@@ -173,11 +649,22 @@ mod tests {
     use super::*;
     #[test]
     fn smoke() {
-        let a = now_wall();
+        let clock = RealClock;
+        let a = clock.now_wall();
         std::thread::sleep(Duration::from_millis(10));
-        let b = now_wall();
+        let b = clock.now_wall();
         assert!(b > a);
     }
+
+    #[test]
+    fn mock_clock_advances_deterministically() {
+        let (under_test, driver) = MockClock::handle_pair();
+        assert_eq!(under_test.now_mono(), Duration::ZERO);
+        driver.increment(Duration::from_secs(1));
+        assert_eq!(under_test.now_mono(), Duration::from_secs(1));
+        driver.set(Duration::from_secs(5));
+        assert_eq!(under_test.now_wall(), Duration::from_secs(5));
+    }
 }
 
 fn vdso_get_time(clock: libc::clockid_t) -> u64 {
@@ -191,9 +678,11 @@ fn vdso_get_time(clock: libc::clockid_t) -> u64 {
 }
 
 fn main() {
+    let clock = RealClock;
     for _ in 0..10000 {
         let _ = vdso_get_time(CLOCK_REALTIME);
-        let _ = now_wall();
+        let _ = clock.now_wall();
+        let _ = now_wall(Precision::Coarse);
     }
 
     // 测试 CLOCK_REALTIME
@@ -209,12 +698,22 @@ fn main() {
     let start = Instant::now();
     let mut count = 0;
     while start.elapsed().as_secs() < 5 {
-        let _ = now_wall();
+        let _ = clock.now_wall();
         count += 1;
     }
     let tsc_qps = count as f64 / start.elapsed().as_secs_f64();
 
+    // 测试 _COARSE 变体（jiffy 级精度，预期 QPS 远高于上面两者）
+    let start = Instant::now();
+    let mut count = 0;
+    while start.elapsed().as_secs() < 5 {
+        let _ = now_wall(Precision::Coarse);
+        count += 1;
+    }
+    let coarse_qps = count as f64 / start.elapsed().as_secs_f64();
+
     println!("vdso_qps: {:.0} QPS", vdso_qps);
     println!("chrono_qps:  {:.0} QPS", tsc_qps);
+    println!("coarse_qps:  {:.0} QPS", coarse_qps);
     println!("  性能差异: {:.2}%", (vdso_qps - tsc_qps) / tsc_qps * 100.0);
 }
@@ -0,0 +1,274 @@
+// ==========================================================
+// 直接解析 vDSO 符号表，绕开 libc::clock_gettime 的 PLT/wrapper 开销
+// ==========================================================
+// `tsc_vs_vdso` 里几个 `vdso_clock_gettime_*` 函数名字里带“vdso”，但如果
+// 只走 libc 的 `clock_gettime` 包装，内核的 vDSO 会不会被命中完全取决于
+// glibc/musl 内部有没有把这个符号指向 vDSO 映射，我们自己并不知道。
+// 这里自己解析 `AT_SYSINFO_EHDR` 指向的 vDSO ELF 镜像，在它的动态符号表
+// 里找到 `__vdso_clock_gettime`（以及 `__vdso_gettimeofday` 作为退路），
+// 把函数指针缓存进 `OnceLock`，之后直接通过指针调用，彻底跳过 libc。
+// 解析失败（比如 seccomp 禁用了 vDSO）时退回 `libc::clock_gettime`。
+//
+// 这份实现被 `src/tsc_vs_vdso.rs` 和 `benches/time_benchmark.rs` 共用，
+// 放进 lib 里是为了避免 ELF/GNU hash 解析这种 unsafe 代码被复制两份、
+// 两边各自维护容易跑偏。
+
+use std::ffi::CStr;
+use std::fs;
+use std::os::raw::c_void;
+use std::sync::OnceLock;
+
+const AT_SYSINFO_EHDR: u64 = 33;
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const DT_NULL: i64 = 0;
+const DT_HASH: i64 = 4;
+const DT_STRTAB: i64 = 5;
+const DT_SYMTAB: i64 = 6;
+const DT_GNU_HASH: i64 = 0x6ffffef5;
+
+#[repr(C)]
+struct Elf64Ehdr {
+    e_ident: [u8; 16],
+    e_type: u16,
+    e_machine: u16,
+    e_version: u32,
+    e_entry: u64,
+    e_phoff: u64,
+    e_shoff: u64,
+    e_flags: u32,
+    e_ehsize: u16,
+    e_phentsize: u16,
+    e_phnum: u16,
+    e_shentsize: u16,
+    e_shnum: u16,
+    e_shstrndx: u16,
+}
+
+#[repr(C)]
+struct Elf64Phdr {
+    p_type: u32,
+    p_flags: u32,
+    p_offset: u64,
+    p_vaddr: u64,
+    p_paddr: u64,
+    p_filesz: u64,
+    p_memsz: u64,
+    p_align: u64,
+}
+
+#[repr(C)]
+struct Elf64Dyn {
+    d_tag: i64,
+    d_val: u64,
+}
+
+#[repr(C)]
+struct Elf64Sym {
+    st_name: u32,
+    st_info: u8,
+    st_other: u8,
+    st_shndx: u16,
+    st_value: u64,
+    st_size: u64,
+}
+
+/// 读取 `/proc/self/auxv`（`(a_type, a_val)` 的 `u64` 对，以
+/// `AT_NULL` 结尾），找到内核映射 vDSO 的 `AT_SYSINFO_EHDR` 条目。
+fn find_vdso_base() -> Option<*const u8> {
+    let bytes = fs::read("/proc/self/auxv").ok()?;
+    for chunk in bytes.chunks_exact(16) {
+        let a_type = u64::from_ne_bytes(chunk[0..8].try_into().ok()?);
+        let a_val = u64::from_ne_bytes(chunk[8..16].try_into().ok()?);
+        if a_type == 0 {
+            break;
+        }
+        if a_type == AT_SYSINFO_EHDR && a_val != 0 {
+            return Some(a_val as *const u8);
+        }
+    }
+    None
+}
+
+/// GNU hash（djb2 变体），用于在 `.gnu.hash` 里定位符号。
+fn gnu_hash(name: &[u8]) -> u32 {
+    let mut h: u32 = 5381;
+    for &b in name {
+        h = h.wrapping_mul(33).wrapping_add(b as u32);
+    }
+    h
+}
+
+/// 在 vDSO 的动态符号表里查找 `name`，返回其运行时地址。优先走
+/// `.gnu.hash`，vDSO 只导出 SysV `.hash`（没有 `.gnu.hash`）时退回按
+/// `DT_HASH` 给出的符号总数线性扫描 `.dynsym`。
+///
+/// # Safety
+/// 调用方必须保证 `base` 指向一块有效、可读的 vDSO ELF 镜像。
+unsafe fn lookup_symbol(base: *const u8, name: &[u8]) -> Option<usize> {
+    let ehdr = &*(base as *const Elf64Ehdr);
+    if &ehdr.e_ident[0..4] != b"\x7fELF" {
+        return None;
+    }
+
+    // load_bias：把 ELF 里的虚拟地址换算成本进程里的运行时地址所需
+    // 加的偏移量。推导：AT_SYSINFO_EHDR 给出的 `base` 正是文件偏移 0
+    // 被映射到的运行时地址，而文件偏移 0 落在某个 PT_LOAD 段内，其
+    // 运行时地址是 `bias + p_vaddr + (0 - p_offset)`，令其等于 base
+    // 解出 `bias = base + p_offset - p_vaddr`（同 Linux `parse_vdso.c`）。
+    // vDSO 的链接地址可以高于它实际映射的运行时地址（x86_64 上
+    // link-time vaddr 是 `0xffffffffff700000` 这类高地址），这里的
+    // 加减法是模 2^64 的定点运算，不是普通整数算术，必须用 wrapping
+    // 版本，否则 debug 构建会在减法处 panic（release 下只是静默绕回，
+    // 侥幸算对）。
+    let phdrs = (base as usize).wrapping_add(ehdr.e_phoff as usize) as *const Elf64Phdr;
+    let mut load_bias: Option<usize> = None;
+    let mut dynamic_vaddr: Option<u64> = None;
+    for i in 0..ehdr.e_phnum as usize {
+        let ph = &*phdrs.add(i);
+        if ph.p_type == PT_LOAD && load_bias.is_none() {
+            load_bias = Some(
+                (base as usize)
+                    .wrapping_add(ph.p_offset as usize)
+                    .wrapping_sub(ph.p_vaddr as usize),
+            );
+        }
+        if ph.p_type == PT_DYNAMIC {
+            dynamic_vaddr = Some(ph.p_vaddr);
+        }
+    }
+    let load_bias = load_bias?;
+    let dynamic = load_bias.wrapping_add(dynamic_vaddr? as usize) as *const Elf64Dyn;
+
+    let mut strtab: Option<usize> = None;
+    let mut symtab: Option<usize> = None;
+    let mut gnu_hash_tab: Option<usize> = None;
+    let mut sysv_hash_tab: Option<usize> = None;
+    let mut i = 0isize;
+    loop {
+        let d = &*dynamic.offset(i);
+        if d.d_tag == DT_NULL {
+            break;
+        }
+        match d.d_tag {
+            DT_STRTAB => strtab = Some(load_bias.wrapping_add(d.d_val as usize)),
+            DT_SYMTAB => symtab = Some(load_bias.wrapping_add(d.d_val as usize)),
+            DT_GNU_HASH => gnu_hash_tab = Some(load_bias.wrapping_add(d.d_val as usize)),
+            DT_HASH => sysv_hash_tab = Some(load_bias.wrapping_add(d.d_val as usize)),
+            _ => {}
+        }
+        i += 1;
+    }
+    let strtab = strtab? as *const u8;
+    let symtab = symtab? as *const Elf64Sym;
+
+    if let Some(gnu_hash_tab) = gnu_hash_tab {
+        if let Some(addr) = lookup_via_gnu_hash(gnu_hash_tab, symtab, strtab, load_bias, name) {
+            return Some(addr);
+        }
+    }
+    if let Some(sysv_hash_tab) = sysv_hash_tab {
+        return lookup_via_sysv_hash(sysv_hash_tab, symtab, strtab, load_bias, name);
+    }
+    None
+}
+
+/// `.gnu.hash` 查找路径：布隆过滤器 + 桶/链表，常数时间命中或排除。
+///
+/// # Safety
+/// 调用方必须保证 `gnu_hash_tab`/`symtab`/`strtab` 都指向同一个有效的
+/// vDSO ELF 镜像里对应的表。
+unsafe fn lookup_via_gnu_hash(
+    gnu_hash_tab: usize,
+    symtab: *const Elf64Sym,
+    strtab: *const u8,
+    load_bias: usize,
+    name: &[u8],
+) -> Option<usize> {
+    // .gnu.hash 头：nbuckets, symoffset, bloom_size, bloom_shift
+    let nbuckets = *(gnu_hash_tab as *const u32);
+    let symoffset = *(gnu_hash_tab.wrapping_add(4) as *const u32);
+    let bloom_size = *(gnu_hash_tab.wrapping_add(8) as *const u32);
+    let bloom_shift = *(gnu_hash_tab.wrapping_add(12) as *const u32);
+
+    let bloom = gnu_hash_tab.wrapping_add(16) as *const u64;
+    let h = gnu_hash(name);
+    let word = *bloom.add((h as usize / 64) % bloom_size as usize);
+    let mask = (1u64 << (h % 64)) | (1u64 << ((h >> bloom_shift) % 64));
+    if word & mask != mask {
+        // 布隆过滤器确定符号不存在
+        return None;
+    }
+
+    let buckets = (bloom as usize).wrapping_add(bloom_size as usize * 8) as *const u32;
+    let mut idx = *buckets.add(h as usize % nbuckets as usize);
+    if idx == 0 {
+        return None;
+    }
+    let chain = (buckets as usize).wrapping_add(nbuckets as usize * 4) as *const u32;
+    loop {
+        let chain_hash = *chain.add((idx - symoffset) as usize);
+        if (chain_hash | 1) == (h | 1) {
+            let sym = &*symtab.add(idx as usize);
+            let sym_name = CStr::from_ptr(strtab.add(sym.st_name as usize) as *const i8);
+            if sym_name.to_bytes() == name {
+                return Some(load_bias.wrapping_add(sym.st_value as usize));
+            }
+        }
+        if chain_hash & 1 != 0 {
+            // chain 的最后一个符号
+            break;
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// `.hash`（SysV）查找路径：没有 `.gnu.hash` 的 vDSO 退回这条路。
+/// `DT_HASH` 表头的 `nchain` 就是 `.dynsym` 里符号总数的上界，直接
+/// 拿它做线性扫描的边界，不用另外重新实现一遍桶/链表查找。
+///
+/// # Safety
+/// 调用方必须保证 `sysv_hash_tab`/`symtab`/`strtab` 都指向同一个有效的
+/// vDSO ELF 镜像里对应的表。
+unsafe fn lookup_via_sysv_hash(
+    sysv_hash_tab: usize,
+    symtab: *const Elf64Sym,
+    strtab: *const u8,
+    load_bias: usize,
+    name: &[u8],
+) -> Option<usize> {
+    let nchain = *(sysv_hash_tab.wrapping_add(4) as *const u32);
+    for idx in 0..nchain as usize {
+        let sym = &*symtab.add(idx);
+        if sym.st_name == 0 {
+            continue;
+        }
+        let sym_name = CStr::from_ptr(strtab.add(sym.st_name as usize) as *const i8);
+        if sym_name.to_bytes() == name {
+            return Some(load_bias.wrapping_add(sym.st_value as usize));
+        }
+    }
+    None
+}
+
+fn resolve(name: &[u8]) -> Option<usize> {
+    let base = find_vdso_base()?;
+    unsafe { lookup_symbol(base, name) }
+}
+
+pub type ClockGettimeFn =
+    unsafe extern "C" fn(libc::clockid_t, *mut libc::timespec) -> libc::c_int;
+pub type GettimeofdayFn = unsafe extern "C" fn(*mut libc::timeval, *mut c_void) -> libc::c_int;
+
+pub fn clock_gettime_fn() -> Option<ClockGettimeFn> {
+    static RESOLVED: OnceLock<Option<usize>> = OnceLock::new();
+    let addr = *RESOLVED.get_or_init(|| resolve(b"__vdso_clock_gettime"));
+    addr.map(|a| unsafe { std::mem::transmute::<usize, ClockGettimeFn>(a) })
+}
+
+pub fn gettimeofday_fn() -> Option<GettimeofdayFn> {
+    static RESOLVED: OnceLock<Option<usize>> = OnceLock::new();
+    let addr = *RESOLVED.get_or_init(|| resolve(b"__vdso_gettimeofday"));
+    addr.map(|a| unsafe { std::mem::transmute::<usize, GettimeofdayFn>(a) })
+}